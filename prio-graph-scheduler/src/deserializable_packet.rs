@@ -36,4 +36,14 @@ pub trait DeserializableTxPacket: PartialEq + PartialOrd + Eq + Sized {
     fn compute_unit_price(&self) -> u64;
 
     fn compute_unit_limit(&self) -> u64;
+
+    /// Stake of the transaction's sender. Used to break ties between
+    /// transactions of otherwise-equal priority, so that zero-fee spam
+    /// can't compete evenly with higher-staked senders for scheduling.
+    ///
+    /// Stake isn't carried on the network packet itself - implementations
+    /// are expected to resolve it from a bank/stake-map lookup keyed by the
+    /// sanitized transaction's fee payer (and cache the result, since that
+    /// lookup isn't free) rather than reading it off `original_packet()`.
+    fn sender_stake(&self) -> u64;
 }
\ No newline at end of file
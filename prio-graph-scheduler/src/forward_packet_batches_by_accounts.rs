@@ -0,0 +1,234 @@
+use {
+    crate::deserializable_packet::DeserializableTxPacket,
+    solana_sdk::pubkey::Pubkey,
+    std::{collections::HashMap, sync::Arc},
+};
+
+/// Default per-account compute-unit ceiling for a single forwarded batch.
+/// Chosen so that no single hot account can dominate a batch the next
+/// leader has to process.
+pub const DEFAULT_FORWARD_BATCH_PER_ACCOUNT_CU_LIMIT: u64 = 12_000_000;
+
+/// Default total compute-unit ceiling for a single forwarded batch,
+/// mirroring a block's compute budget.
+pub const DEFAULT_FORWARD_BATCH_CU_LIMIT: u64 = 48_000_000;
+
+/// A single forwardable batch, tracking compute-unit usage per
+/// write-locked account so a subsequent packet can be rejected before it's
+/// added if it would push any account over the cap.
+struct ForwardBatch<Tx> {
+    packets: Vec<Arc<Tx>>,
+    account_cu_totals: HashMap<Pubkey, u64>,
+    total_cu: u64,
+}
+
+impl<Tx> Default for ForwardBatch<Tx> {
+    fn default() -> Self {
+        Self {
+            packets: Vec::new(),
+            account_cu_totals: HashMap::new(),
+            total_cu: 0,
+        }
+    }
+}
+
+impl<Tx> ForwardBatch<Tx> {
+    fn can_fit(
+        &self,
+        write_locks: &[Pubkey],
+        cu_limit: u64,
+        account_cu_limit: u64,
+        batch_cu_limit: u64,
+    ) -> bool {
+        if self.total_cu.saturating_add(cu_limit) > batch_cu_limit {
+            return false;
+        }
+        write_locks.iter().all(|account| {
+            let current = self.account_cu_totals.get(account).copied().unwrap_or(0);
+            current.saturating_add(cu_limit) <= account_cu_limit
+        })
+    }
+
+    fn insert(&mut self, packet: Arc<Tx>, write_locks: &[Pubkey], cu_limit: u64) {
+        for account in write_locks {
+            *self.account_cu_totals.entry(*account).or_insert(0) += cu_limit;
+        }
+        self.total_cu = self.total_cu.saturating_add(cu_limit);
+        self.packets.push(packet);
+    }
+}
+
+/// Packs to-be-forwarded transactions into batches while enforcing a
+/// per-write-account compute-unit ceiling and a block-level compute-unit
+/// ceiling, so a single hot account can't dominate a forwarded batch and
+/// the next leader gets a fair, bounded pile of work instead of a
+/// priority-skewed one that will just conflict on replay.
+///
+/// Packets are expected to be fed in priority order (highest first); each
+/// is greedily added to the current batch, or starts a new batch once the
+/// current one is full.
+pub struct ForwardPacketBatchesByAccounts<Tx: DeserializableTxPacket> {
+    batches: Vec<ForwardBatch<Tx>>,
+    current_batch: ForwardBatch<Tx>,
+    account_cu_limit: u64,
+    batch_cu_limit: u64,
+}
+
+impl<Tx: DeserializableTxPacket> ForwardPacketBatchesByAccounts<Tx> {
+    pub fn new(account_cu_limit: u64, batch_cu_limit: u64) -> Self {
+        Self {
+            batches: Vec::new(),
+            current_batch: ForwardBatch::default(),
+            account_cu_limit,
+            batch_cu_limit,
+        }
+    }
+
+    pub fn new_with_default_limits() -> Self {
+        Self::new(
+            DEFAULT_FORWARD_BATCH_PER_ACCOUNT_CU_LIMIT,
+            DEFAULT_FORWARD_BATCH_CU_LIMIT,
+        )
+    }
+
+    /// Attempts to add a packet to the current batch, starting a new batch
+    /// if it doesn't fit. Returns `false` if the packet can't be forwarded
+    /// at all, because it alone would exceed the per-account or batch cap.
+    pub fn add_packet(&mut self, packet: &Arc<Tx>) -> bool {
+        let write_locks = writable_accounts(packet);
+        let cu_limit = packet.compute_unit_limit();
+
+        if self
+            .current_batch
+            .can_fit(&write_locks, cu_limit, self.account_cu_limit, self.batch_cu_limit)
+        {
+            self.current_batch.insert(packet.clone(), &write_locks, cu_limit);
+            return true;
+        }
+
+        let mut new_batch = ForwardBatch::default();
+        if !new_batch.can_fit(&write_locks, cu_limit, self.account_cu_limit, self.batch_cu_limit) {
+            return false;
+        }
+
+        let finished_batch = std::mem::replace(&mut self.current_batch, new_batch);
+        if !finished_batch.packets.is_empty() {
+            self.batches.push(finished_batch);
+        }
+        self.current_batch.insert(packet.clone(), &write_locks, cu_limit);
+        true
+    }
+
+    /// Drains all completed and in-progress batches of packets, leaving the
+    /// builder empty and ready to pack another round of batches.
+    pub fn take_batches(&mut self) -> Vec<Vec<Arc<Tx>>> {
+        let mut batches = std::mem::take(&mut self.batches);
+        let current = std::mem::take(&mut self.current_batch);
+        if !current.packets.is_empty() {
+            batches.push(current);
+        }
+        batches.into_iter().map(|batch| batch.packets).collect()
+    }
+}
+
+/// Returns the writable account locks for a packet's sanitized transaction.
+fn writable_accounts<Tx: DeserializableTxPacket>(packet: &Arc<Tx>) -> Vec<Pubkey> {
+    let message = packet.transaction().get_message();
+    message
+        .message
+        .static_account_keys()
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| message.message.is_maybe_writable(*index, None))
+        .map(|(_, pubkey)| *pubkey)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::tests::MockImmutableDeserializedPacket,
+        solana_perf::packet::Packet,
+        solana_sdk::{
+            hash::Hash, signature::Keypair, signer::Signer, system_transaction,
+            transaction::{SanitizedVersionedTransaction, VersionedTransaction},
+        },
+    };
+
+    /// Builds a mock packet transferring from `from` to `to`, so tests can
+    /// control exactly which accounts a packet write-locks. Both accounts
+    /// end up writable: `from` as the fee payer, `to` as the transfer
+    /// destination.
+    fn mock_packet(
+        from: &Keypair,
+        to: &Pubkey,
+        compute_unit_price: u64,
+        compute_unit_limit: u32,
+    ) -> Arc<MockImmutableDeserializedPacket> {
+        let transaction = system_transaction::transfer(from, to, 1, Hash::default());
+        let sanitized =
+            SanitizedVersionedTransaction::try_from(VersionedTransaction::from(transaction))
+                .unwrap();
+        Arc::new(MockImmutableDeserializedPacket {
+            original_packet: Packet::default(),
+            transaction: sanitized,
+            message_hash: Hash::default(),
+            is_simple_vote: false,
+            compute_unit_price,
+            compute_unit_limit,
+            sender_stake: 0,
+        })
+    }
+
+    #[test]
+    fn test_single_packet_over_cap_is_rejected() {
+        let mut batches = ForwardPacketBatchesByAccounts::new(100, 1_000);
+        let from = Keypair::new();
+        let to = Pubkey::new_unique();
+
+        // A single packet whose own compute-unit limit already exceeds the
+        // per-account cap can never fit in any batch.
+        let packet = mock_packet(&from, &to, 1, 200);
+        assert!(!batches.add_packet(&packet));
+        assert!(batches.take_batches().is_empty());
+    }
+
+    #[test]
+    fn test_batch_rolls_over_once_full() {
+        let mut batches = ForwardPacketBatchesByAccounts::new(1_000, 150);
+        let first = mock_packet(&Keypair::new(), &Pubkey::new_unique(), 1, 100);
+        let second = mock_packet(&Keypair::new(), &Pubkey::new_unique(), 1, 100);
+
+        // Each packet fits the batch cap alone, but together they don't, so
+        // the second packet should start a fresh batch rather than being
+        // rejected.
+        assert!(batches.add_packet(&first));
+        assert!(batches.add_packet(&second));
+
+        let taken = batches.take_batches();
+        assert_eq!(taken.len(), 2);
+        assert_eq!(taken[0].len(), 1);
+        assert_eq!(taken[1].len(), 1);
+    }
+
+    #[test]
+    fn test_per_account_cap_enforced_across_multiple_inserts() {
+        let mut batches = ForwardPacketBatchesByAccounts::new(150, 1_000);
+        let shared_to = Pubkey::new_unique();
+        let first = mock_packet(&Keypair::new(), &shared_to, 1, 100);
+        let second = mock_packet(&Keypair::new(), &shared_to, 1, 100);
+
+        // Both packets write-lock `shared_to`; together they'd push that
+        // account's running total past the per-account cap, even though
+        // neither exceeds the batch-wide cap alone, so the second packet
+        // should roll over into a new batch.
+        assert!(batches.add_packet(&first));
+        assert!(batches.add_packet(&second));
+
+        let taken = batches.take_batches();
+        assert_eq!(taken.len(), 2);
+        assert_eq!(taken[0].len(), 1);
+        assert_eq!(taken[1].len(), 1);
+    }
+}
@@ -0,0 +1,28 @@
+/// Simple monotonically increasing id generator.
+/// Not thread-safe - each generator should be owned by a single thread/struct.
+#[derive(Default)]
+pub struct IdGenerator {
+    next_id: u64,
+}
+
+impl IdGenerator {
+    /// Generate a new id.
+    pub fn next(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_generator() {
+        let mut generator = IdGenerator::default();
+        assert_eq!(generator.next(), 0);
+        assert_eq!(generator.next(), 1);
+        assert_eq!(generator.next(), 2);
+    }
+}
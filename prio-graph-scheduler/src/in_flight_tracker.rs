@@ -0,0 +1,47 @@
+use {
+    crate::scheduler_messages::{TransactionBatchId, TransactionId},
+    std::collections::HashMap,
+};
+
+/// Tracks the number of transactions and batches that have been sent to
+/// each worker thread but have not yet completed, so the scheduler knows
+/// how much outstanding work each thread has.
+#[derive(Default)]
+pub struct InFlightTracker {
+    num_in_flight_per_thread: Vec<usize>,
+    batches: HashMap<TransactionBatchId, (usize, Vec<TransactionId>)>,
+}
+
+impl InFlightTracker {
+    pub fn new(num_threads: usize) -> Self {
+        Self {
+            num_in_flight_per_thread: vec![0; num_threads],
+            batches: HashMap::new(),
+        }
+    }
+
+    pub fn num_in_flight_per_thread(&self) -> &[usize] {
+        &self.num_in_flight_per_thread
+    }
+
+    pub fn track_batch(
+        &mut self,
+        batch_id: TransactionBatchId,
+        ids: Vec<TransactionId>,
+        thread_id: usize,
+    ) {
+        self.num_in_flight_per_thread[thread_id] += ids.len();
+        self.batches.insert(batch_id, (thread_id, ids));
+    }
+
+    /// Marks the batch as complete, returning the thread it was on and the
+    /// transaction ids that were part of it.
+    pub fn complete_batch(&mut self, batch_id: TransactionBatchId) -> (usize, Vec<TransactionId>) {
+        let (thread_id, ids) = self
+            .batches
+            .remove(&batch_id)
+            .expect("batch_id should exist in in-flight tracker");
+        self.num_in_flight_per_thread[thread_id] -= ids.len();
+        (thread_id, ids)
+    }
+}
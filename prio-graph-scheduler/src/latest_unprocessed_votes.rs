@@ -0,0 +1,223 @@
+use {
+    crate::deserializable_packet::DeserializableTxPacket,
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
+    solana_vote_program::vote_instruction::VoteInstruction,
+    std::{collections::HashMap, sync::Arc},
+};
+
+/// A single validator's most recent unprocessed vote.
+struct LatestVote<Tx> {
+    packet: Arc<Tx>,
+    slot: Slot,
+}
+
+/// Buffers simple-vote transactions separately from the main transaction
+/// container, keyed by the voting validator's vote account pubkey.
+///
+/// Since only the most recent vote from a validator is ever relevant to
+/// consensus, a new vote from a validator always supersedes its previous
+/// one. This keeps one validator's repeated votes from crowding out other
+/// traffic in the main priority-ordered buffer, and lets the scheduler pull
+/// votes directly without running them through conflict analysis.
+#[derive(Default)]
+pub struct LatestUnprocessedVotes<Tx: DeserializableTxPacket> {
+    latest_votes_per_pubkey: HashMap<Pubkey, LatestVote<Tx>>,
+}
+
+impl<Tx: DeserializableTxPacket> LatestUnprocessedVotes<Tx> {
+    pub fn len(&self) -> usize {
+        self.latest_votes_per_pubkey.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.latest_votes_per_pubkey.is_empty()
+    }
+
+    /// Inserts a vote packet, evicting the previously stored vote for the
+    /// same validator if the incoming one is for a more recent slot. Votes
+    /// for a pubkey we haven't seen, or for a slot at least as recent as
+    /// what's stored, replace the existing entry; older votes are dropped.
+    pub fn insert(&mut self, packet: Arc<Tx>) {
+        let Some(vote_pubkey) = vote_account_key(&packet) else {
+            return;
+        };
+        let slot = latest_vote_slot(&packet).unwrap_or(0);
+
+        match self.latest_votes_per_pubkey.entry(vote_pubkey) {
+            std::collections::hash_map::Entry::Occupied(mut entry) => {
+                if slot >= entry.get().slot {
+                    entry.insert(LatestVote { packet, slot });
+                }
+            }
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(LatestVote { packet, slot });
+            }
+        }
+    }
+
+    /// Drains all currently buffered votes, returning their packets. Since
+    /// every entry is already the latest vote for its validator, everything
+    /// remaining is forwardable.
+    pub fn drain_forwardable_packets(&mut self) -> Vec<Arc<Tx>> {
+        self.latest_votes_per_pubkey
+            .drain()
+            .map(|(_, vote)| vote.packet)
+            .collect()
+    }
+}
+
+/// Extracts the voting validator's vote account pubkey from the vote-program
+/// instruction's account list.
+///
+/// The message's *first writable account* is the fee payer, not necessarily
+/// the vote account - a vote-relay or hot-wallet setup can have one fee
+/// payer submit votes on behalf of many validators, in which case keying off
+/// the fee payer would collapse all of those validators into a single
+/// dedup bucket and silently drop every vote but the last. The vote account
+/// is instead read from a fixed position (account index 0) within the
+/// vote-program instruction itself, which [`VoteInstruction`]'s constructors
+/// always place there regardless of who pays or signs.
+fn vote_account_key<Tx: DeserializableTxPacket>(packet: &Tx) -> Option<Pubkey> {
+    let message = packet.transaction().get_message();
+    let account_keys = message.message.static_account_keys();
+    message
+        .program_instructions_iter()
+        .find(|(program_id, _)| **program_id == solana_vote_program::id())
+        .and_then(|(_, ix)| ix.accounts.first())
+        .and_then(|index| account_keys.get(*index as usize))
+        .copied()
+}
+
+/// Best-effort extraction of the slot being voted for, used to compare
+/// recency between two votes from the same validator. Falls back to `None`
+/// if no vote-program instruction is present or it can't be decoded;
+/// [`LatestUnprocessedVotes::insert`] treats that as slot 0, so such a vote
+/// only replaces a stored vote that is also at slot 0.
+///
+/// Only instructions addressed to the vote program are considered - bincode
+/// enum decoding is permissive enough that an unrelated instruction (e.g. a
+/// compute-budget instruction) can spuriously decode as a `VoteInstruction`,
+/// which would corrupt this recency comparison.
+fn latest_vote_slot<Tx: DeserializableTxPacket>(packet: &Tx) -> Option<Slot> {
+    let message = packet.transaction().get_message();
+    message
+        .program_instructions_iter()
+        .filter(|(program_id, _)| **program_id == solana_vote_program::id())
+        .find_map(|(_, ix)| {
+            let vote_instruction: VoteInstruction = bincode::deserialize(&ix.data).ok()?;
+            vote_instruction.last_voted_slot()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::tests::MockImmutableDeserializedPacket,
+        solana_perf::packet::Packet,
+        solana_sdk::{
+            hash::Hash,
+            message::Message,
+            signature::{Keypair, Signer},
+            transaction::{SanitizedVersionedTransaction, Transaction, VersionedTransaction},
+        },
+        solana_vote_program::{vote_instruction, vote_state::Vote},
+    };
+
+    /// Builds a mock simple-vote packet for `vote_pubkey`/`authorized_voter`
+    /// voting on `slot`, paid for by `fee_payer`. `compute_unit_price` is
+    /// set to a caller-chosen value purely so tests can tell which of
+    /// several inserted packets survived a dedup.
+    fn mock_vote_packet(
+        vote_pubkey: &Pubkey,
+        authorized_voter: &Keypair,
+        fee_payer: &Keypair,
+        slot: Slot,
+        compute_unit_price: u64,
+    ) -> Arc<MockImmutableDeserializedPacket> {
+        let vote_ix = vote_instruction::vote(
+            vote_pubkey,
+            &authorized_voter.pubkey(),
+            Vote::new(vec![slot], Hash::default()),
+        );
+        let message = Message::new(&[vote_ix], Some(&fee_payer.pubkey()));
+        let transaction = Transaction::new(&[fee_payer, authorized_voter], message, Hash::default());
+        let sanitized =
+            SanitizedVersionedTransaction::try_from(VersionedTransaction::from(transaction))
+                .unwrap();
+        Arc::new(MockImmutableDeserializedPacket {
+            original_packet: Packet::default(),
+            transaction: sanitized,
+            message_hash: Hash::default(),
+            is_simple_vote: true,
+            compute_unit_price,
+            compute_unit_limit: 0,
+            sender_stake: 0,
+        })
+    }
+
+    #[test]
+    fn test_vote_account_key_uses_vote_account_not_fee_payer() {
+        let vote_pubkey = Pubkey::new_unique();
+        let authorized_voter = Keypair::new();
+        // Two unrelated fee payers submitting on behalf of the same
+        // validator, as a vote-relay/hot-wallet setup would.
+        let relay_one = Keypair::new();
+        let relay_two = Keypair::new();
+
+        let mut votes = LatestUnprocessedVotes::<MockImmutableDeserializedPacket>::default();
+        votes.insert(mock_vote_packet(&vote_pubkey, &authorized_voter, &relay_one, 1, 1));
+        votes.insert(mock_vote_packet(&vote_pubkey, &authorized_voter, &relay_two, 2, 2));
+
+        // Both votes are keyed by the same vote account despite different
+        // fee payers, so they should dedup into a single entry rather than
+        // being tracked as two different validators.
+        assert_eq!(votes.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_keeps_latest_slot() {
+        let vote_pubkey = Pubkey::new_unique();
+        let authorized_voter = Keypair::new();
+        let fee_payer = Keypair::new();
+
+        let mut votes = LatestUnprocessedVotes::<MockImmutableDeserializedPacket>::default();
+        votes.insert(mock_vote_packet(&vote_pubkey, &authorized_voter, &fee_payer, 5, 1));
+        votes.insert(mock_vote_packet(&vote_pubkey, &authorized_voter, &fee_payer, 10, 2));
+        // An older, stale vote arriving after the latest one must not
+        // displace it.
+        votes.insert(mock_vote_packet(&vote_pubkey, &authorized_voter, &fee_payer, 7, 3));
+
+        assert_eq!(votes.len(), 1);
+        let drained = votes.drain_forwardable_packets();
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].compute_unit_price, 2);
+    }
+
+    #[test]
+    fn test_insert_tracks_different_validators_separately() {
+        let fee_payer = Keypair::new();
+        let validator_one = (Pubkey::new_unique(), Keypair::new());
+        let validator_two = (Pubkey::new_unique(), Keypair::new());
+
+        let mut votes = LatestUnprocessedVotes::<MockImmutableDeserializedPacket>::default();
+        votes.insert(mock_vote_packet(&validator_one.0, &validator_one.1, &fee_payer, 1, 1));
+        votes.insert(mock_vote_packet(&validator_two.0, &validator_two.1, &fee_payer, 1, 2));
+
+        assert_eq!(votes.len(), 2);
+    }
+
+    #[test]
+    fn test_drain_forwardable_packets_empties_storage() {
+        let vote_pubkey = Pubkey::new_unique();
+        let authorized_voter = Keypair::new();
+        let fee_payer = Keypair::new();
+
+        let mut votes = LatestUnprocessedVotes::<MockImmutableDeserializedPacket>::default();
+        votes.insert(mock_vote_packet(&vote_pubkey, &authorized_voter, &fee_payer, 1, 1));
+
+        let drained = votes.drain_forwardable_packets();
+        assert_eq!(drained.len(), 1);
+        assert!(votes.is_empty());
+    }
+}
@@ -1,6 +1,8 @@
 //! Solana Priority Graph Scheduler.
+pub mod forward_packet_batches_by_accounts;
 pub mod id_generator;
 pub mod in_flight_tracker;
+pub mod latest_unprocessed_votes;
 pub mod scheduler_error;
 pub mod scheduler_messages;
 pub mod scheduler_metrics;
@@ -74,6 +76,10 @@ mod tests {
         pub is_simple_vote: bool,
         pub compute_unit_price: u64,
         pub compute_unit_limit: u32,
+        /// Stake of the transaction's sender. Unlike the other fields here,
+        /// this isn't derived from the packet - tests set it directly to
+        /// exercise stake-based tie-breaking.
+        pub sender_stake: u64,
     }
 
     impl DeserializableTxPacket for MockImmutableDeserializedPacket {
@@ -111,6 +117,12 @@ mod tests {
                 is_simple_vote,
                 compute_unit_price,
                 compute_unit_limit,
+                // Not derivable from the packet - see `sender_stake`'s doc
+                // comment on `DeserializableTxPacket`. Real implementations
+                // resolve this from a bank/stake-map lookup, which `new`
+                // doesn't have access to; this mock defaults it to zero and
+                // lets tests override it directly when stake matters.
+                sender_stake: 0,
             })
         }
 
@@ -138,6 +150,10 @@ mod tests {
             u64::from(self.compute_unit_limit)
         }
 
+        fn sender_stake(&self) -> u64 {
+            self.sender_stake
+        }
+
         // This function deserializes packets into transactions, computes the blake3 hash of transaction
         // messages.
         fn build_sanitized_transaction(
@@ -184,10 +200,22 @@ mod tests {
         }
     }
 
+    impl MockImmutableDeserializedPacket {
+        /// Effective scheduling priority: total reward
+        /// (`compute_unit_price * compute_unit_limit`, saturating) rather
+        /// than raw per-CU price, so ranking reflects total fee within a
+        /// block's CU budget instead of rewarding a tiny high-price
+        /// instruction.
+        fn priority(&self) -> u64 {
+            self.compute_unit_price()
+                .saturating_mul(self.compute_unit_limit())
+        }
+    }
+
     // PartialEq MUST be consistent with PartialOrd and Ord
     impl PartialEq for MockImmutableDeserializedPacket {
         fn eq(&self, other: &Self) -> bool {
-            self.compute_unit_price() == other.compute_unit_price()
+            self.priority() == other.priority()
         }
     }
 
@@ -199,7 +227,7 @@ mod tests {
 
     impl Ord for MockImmutableDeserializedPacket {
         fn cmp(&self, other: &Self) -> Ordering {
-            self.compute_unit_price().cmp(&other.compute_unit_price())
+            self.priority().cmp(&other.priority())
         }
     }
 
@@ -0,0 +1,362 @@
+use {
+    crate::{
+        deserializable_packet::DeserializableTxPacket,
+        id_generator::IdGenerator,
+        in_flight_tracker::InFlightTracker,
+        latest_unprocessed_votes::LatestUnprocessedVotes,
+        scheduler_error::SchedulerError,
+        scheduler_messages::{TransactionBatchId, TransactionId},
+        scheduler_metrics::SchedulerCountMetrics,
+        thread_aware_account_locks::ThreadAwareAccountLocks,
+        transaction_priority_id::{DefaultPriorityComparator, PriorityComparator, TransactionPriorityDetails},
+        transaction_state_container::TransactionStateContainer,
+    },
+    solana_sdk::pubkey::Pubkey,
+    std::sync::Arc,
+};
+
+/// A batch of work ready to be handed to a worker thread: the ids used to
+/// track each transaction through the scheduler, paired with the actual
+/// packets a worker needs in order to execute them.
+pub struct ScheduledBatch<Tx> {
+    pub batch_id: TransactionBatchId,
+    pub ids: Vec<TransactionId>,
+    pub packets: Vec<Arc<Tx>>,
+}
+
+/// Schedules transactions onto worker threads, respecting account locks for
+/// non-vote transactions via a priority graph and handling simple-vote
+/// transactions through a dedicated, conflict-free fast path.
+pub struct PrioGraphScheduler<Tx: DeserializableTxPacket> {
+    in_flight_tracker: InFlightTracker,
+    account_locks: ThreadAwareAccountLocks,
+    num_threads: usize,
+    /// Latest-vote-only storage. Vote packets never enter `container` and
+    /// are scheduled directly, since only the most recent vote per
+    /// validator is ever relevant.
+    latest_unprocessed_votes: LatestUnprocessedVotes<Tx>,
+    /// Generates ids for vote batches, which never pass through a
+    /// `TransactionStateContainer` and so need their own id space.
+    vote_id_generator: IdGenerator,
+    next_batch_id: u64,
+    /// Breaks ties between transactions the container's priority queue
+    /// considers equal. Defaults to `(priority, sender_stake)` ordering;
+    /// operators can supply an alternative policy via
+    /// [`Self::with_comparator`].
+    comparator: Box<dyn PriorityComparator>,
+    /// Counters reported periodically, including drops from a
+    /// fixed-capacity container.
+    metrics: SchedulerCountMetrics,
+}
+
+impl<Tx: DeserializableTxPacket> PrioGraphScheduler<Tx> {
+    pub fn new(num_threads: usize) -> Self {
+        Self {
+            in_flight_tracker: InFlightTracker::new(num_threads),
+            account_locks: ThreadAwareAccountLocks::new(num_threads),
+            num_threads,
+            latest_unprocessed_votes: LatestUnprocessedVotes::default(),
+            vote_id_generator: IdGenerator::default(),
+            next_batch_id: 0,
+            comparator: Box::new(DefaultPriorityComparator),
+            metrics: SchedulerCountMetrics::default(),
+        }
+    }
+
+    /// Counters accumulated since the last [`SchedulerCountMetrics::reset`].
+    pub fn metrics(&self) -> &SchedulerCountMetrics {
+        &self.metrics
+    }
+
+    /// Builds a scheduler that orders equal-priority transactions using a
+    /// custom policy instead of the default `(priority, sender_stake)`
+    /// ordering.
+    pub fn with_comparator(num_threads: usize, comparator: Box<dyn PriorityComparator>) -> Self {
+        Self {
+            comparator,
+            ..Self::new(num_threads)
+        }
+    }
+
+    /// Buffers an incoming packet, routing it to the vote-only storage or
+    /// the conflict-aware container depending on `is_simple_vote()`. For
+    /// non-vote transactions, priority is derived from total reward
+    /// (`compute_unit_price * compute_unit_limit`) rather than raw per-CU
+    /// price, so the scheduler ranks by reward density instead of being
+    /// gamed by a tiny high-price instruction.
+    pub fn insert_packet(&mut self, container: &mut TransactionStateContainer<Tx>, packet: Arc<Tx>) {
+        if packet.is_simple_vote() {
+            self.latest_unprocessed_votes.insert(packet);
+            return;
+        }
+
+        let priority_details = TransactionPriorityDetails::new(
+            packet.compute_unit_price(),
+            packet.compute_unit_limit(),
+            packet.sender_stake(),
+        );
+
+        if container.capacity().is_some() {
+            let (inserted, evicted) = container.push_with_eviction(packet, priority_details);
+            // A drop happened either if the incoming packet was rejected
+            // outright, or if it bumped an existing one out to make room.
+            if inserted.is_none() || evicted.is_some() {
+                self.metrics.num_dropped_on_capacity += 1;
+            }
+        } else {
+            container.insert_new_transaction(packet, priority_details);
+        }
+    }
+
+    /// Schedules work for worker threads, pulling from vote storage and the
+    /// non-vote container through separate code paths. Votes bypass
+    /// priority-graph conflict analysis entirely, since the scheduler only
+    /// ever needs to forward the latest vote per validator.
+    pub fn schedule(
+        &mut self,
+        container: &mut TransactionStateContainer<Tx>,
+    ) -> Result<Vec<ScheduledBatch<Tx>>, SchedulerError> {
+        let mut batches = Vec::new();
+        batches.extend(self.schedule_votes()?);
+        batches.extend(self.schedule_transactions(container)?);
+        Ok(batches)
+    }
+
+    /// Drains the latest-vote storage directly, without running votes
+    /// through account-lock/conflict analysis, and assigns them a batch id.
+    /// The drained packets are returned alongside their freshly-generated
+    /// ids so a worker can actually retrieve and execute them -- votes
+    /// never enter a `TransactionStateContainer`, so there's nowhere else
+    /// to look them up from.
+    fn schedule_votes(&mut self) -> Result<Vec<ScheduledBatch<Tx>>, SchedulerError> {
+        let packets = self.latest_unprocessed_votes.drain_forwardable_packets();
+        if packets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids = packets
+            .iter()
+            .map(|_| TransactionId::new(self.vote_id_generator.next()))
+            .collect();
+        let batch_id = self.next_batch_id();
+        Ok(vec![ScheduledBatch {
+            batch_id,
+            ids,
+            packets,
+        }])
+    }
+
+    /// Pops transactions from the non-vote container in priority order and
+    /// assigns each to the lowest-numbered thread that can take it without
+    /// conflicting with an account lock already held by another thread
+    /// (locks stay held until [`Self::complete_batch`] releases them),
+    /// producing one batch per thread that ended up with work. Transactions
+    /// whose accounts are locked by more than one thread are left buffered
+    /// in the container to be reconsidered on the next call.
+    ///
+    /// The container's heap orders only by the scalar `priority` (then by
+    /// id), so the popped transactions are re-ordered here via the
+    /// configured [`PriorityComparator`] before assignment, e.g. to give
+    /// higher-staked senders precedence at equal priority.
+    fn schedule_transactions(
+        &mut self,
+        container: &mut TransactionStateContainer<Tx>,
+    ) -> Result<Vec<ScheduledBatch<Tx>>, SchedulerError> {
+        let mut priority_ids = Vec::new();
+        while let Some(priority_id) = container.pop() {
+            priority_ids.push(priority_id);
+        }
+        if priority_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        priority_ids.sort_by(|a, b| {
+            let details_a = container
+                .get_transaction_state(a.id)
+                .expect("container should hold state for a just-popped id")
+                .priority_details();
+            let details_b = container
+                .get_transaction_state(b.id)
+                .expect("container should hold state for a just-popped id")
+                .priority_details();
+            self.comparator.compare(&details_b, &details_a)
+        });
+
+        let mut thread_ids: Vec<Vec<TransactionId>> = vec![Vec::new(); self.num_threads];
+        let mut thread_packets: Vec<Vec<Arc<Tx>>> = vec![Vec::new(); self.num_threads];
+
+        for priority_id in priority_ids {
+            let packet = container
+                .get_transaction_state(priority_id.id)
+                .expect("container should hold state for a just-popped id")
+                .packet()
+                .clone();
+            let (write_locks, read_locks) = transaction_account_locks(packet.as_ref());
+
+            let schedulable = self
+                .account_locks
+                .accounts_schedulable_threads(write_locks.iter(), read_locks.iter());
+            let Some(thread_id) = schedulable.min_thread_id() else {
+                // Its accounts are locked by more than one thread; leave it
+                // buffered rather than blocking on it.
+                container.push_id_into_queue(priority_id);
+                continue;
+            };
+
+            self.account_locks
+                .lock_accounts(write_locks.iter(), read_locks.iter(), thread_id);
+            container
+                .get_mut_transaction_state(priority_id.id)
+                .expect("container should hold state for a just-popped id")
+                .transition_to_pending();
+            thread_ids[thread_id].push(priority_id.id);
+            thread_packets[thread_id].push(packet);
+        }
+
+        let mut batches = Vec::new();
+        for thread_id in 0..self.num_threads {
+            if thread_ids[thread_id].is_empty() {
+                continue;
+            }
+            let ids = std::mem::take(&mut thread_ids[thread_id]);
+            let packets = std::mem::take(&mut thread_packets[thread_id]);
+            let batch_id = self.next_batch_id();
+            self.in_flight_tracker
+                .track_batch(batch_id, ids.clone(), thread_id);
+            batches.push(ScheduledBatch {
+                batch_id,
+                ids,
+                packets,
+            });
+        }
+        Ok(batches)
+    }
+
+    /// Called once a worker reports a batch of transactions as finished,
+    /// releasing the account locks taken to schedule them and removing them
+    /// from in-flight tracking so conflicting transactions can be assigned
+    /// again on the next [`Self::schedule`] call.
+    pub fn complete_batch(
+        &mut self,
+        batch_id: TransactionBatchId,
+        container: &TransactionStateContainer<Tx>,
+    ) {
+        let (_thread_id, ids) = self.in_flight_tracker.complete_batch(batch_id);
+        for id in ids {
+            if let Some(state) = container.get_transaction_state(id) {
+                let (write_locks, read_locks) = transaction_account_locks(state.packet().as_ref());
+                self.account_locks
+                    .unlock_accounts(write_locks.iter(), read_locks.iter());
+            }
+        }
+    }
+
+    fn next_batch_id(&mut self) -> TransactionBatchId {
+        let id = TransactionBatchId::new(self.next_batch_id);
+        self.next_batch_id = self.next_batch_id.wrapping_add(1);
+        id
+    }
+}
+
+/// Splits a transaction's account locks into writable and read-only sets,
+/// for conflict checking against [`ThreadAwareAccountLocks`].
+fn transaction_account_locks<Tx: DeserializableTxPacket>(packet: &Tx) -> (Vec<Pubkey>, Vec<Pubkey>) {
+    let message = packet.transaction().get_message();
+    let mut write_locks = Vec::new();
+    let mut read_locks = Vec::new();
+    for (index, pubkey) in message.message.static_account_keys().iter().enumerate() {
+        if message.message.is_maybe_writable(index, None) {
+            write_locks.push(*pubkey);
+        } else {
+            read_locks.push(*pubkey);
+        }
+    }
+    (write_locks, read_locks)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::tests::MockImmutableDeserializedPacket,
+        solana_perf::packet::Packet,
+        solana_sdk::{
+            hash::Hash, signature::Keypair, system_transaction,
+            transaction::{SanitizedVersionedTransaction, VersionedTransaction},
+        },
+        std::cmp::Ordering,
+    };
+
+    fn mock_packet(
+        compute_unit_price: u64,
+        compute_unit_limit: u32,
+        sender_stake: u64,
+    ) -> Arc<MockImmutableDeserializedPacket> {
+        let from = Keypair::new();
+        let to = Pubkey::new_unique();
+        let transaction = system_transaction::transfer(&from, &to, 1, Hash::default());
+        let sanitized =
+            SanitizedVersionedTransaction::try_from(VersionedTransaction::from(transaction))
+                .unwrap();
+        Arc::new(MockImmutableDeserializedPacket {
+            original_packet: Packet::default(),
+            transaction: sanitized,
+            message_hash: Hash::default(),
+            is_simple_vote: false,
+            compute_unit_price,
+            compute_unit_limit,
+            sender_stake,
+        })
+    }
+
+    /// Breaks ties the opposite way to `DefaultPriorityComparator`, so tests
+    /// can confirm a custom comparator installed via `with_comparator`
+    /// actually takes effect instead of the default always winning.
+    struct ReverseStakePriorityComparator;
+
+    impl PriorityComparator for ReverseStakePriorityComparator {
+        fn compare(&self, a: &TransactionPriorityDetails, b: &TransactionPriorityDetails) -> Ordering {
+            a.priority
+                .cmp(&b.priority)
+                .then_with(|| b.sender_stake.cmp(&a.sender_stake))
+        }
+    }
+
+    #[test]
+    fn test_schedule_transactions_breaks_equal_priority_ties_by_stake() {
+        let mut scheduler = PrioGraphScheduler::<MockImmutableDeserializedPacket>::new(1);
+        let mut container = TransactionStateContainer::default();
+
+        // Equal priority, different stake.
+        scheduler.insert_packet(&mut container, mock_packet(10, 1, 1));
+        scheduler.insert_packet(&mut container, mock_packet(10, 1, 100));
+
+        let batches = scheduler.schedule(&mut container).unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].packets.len(), 2);
+        // The default comparator should let the higher-staked sender win
+        // the tie and be scheduled first.
+        assert_eq!(batches[0].packets[0].sender_stake, 100);
+        assert_eq!(batches[0].packets[1].sender_stake, 1);
+    }
+
+    #[test]
+    fn test_with_comparator_overrides_default_tie_break() {
+        let mut scheduler = PrioGraphScheduler::<MockImmutableDeserializedPacket>::with_comparator(
+            1,
+            Box::new(ReverseStakePriorityComparator),
+        );
+        let mut container = TransactionStateContainer::default();
+
+        scheduler.insert_packet(&mut container, mock_packet(10, 1, 1));
+        scheduler.insert_packet(&mut container, mock_packet(10, 1, 100));
+
+        let batches = scheduler.schedule(&mut container).unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].packets.len(), 2);
+        // With the inverted comparator installed, the lower-staked sender
+        // should win the tie instead.
+        assert_eq!(batches[0].packets[0].sender_stake, 1);
+        assert_eq!(batches[0].packets[1].sender_stake, 100);
+    }
+}
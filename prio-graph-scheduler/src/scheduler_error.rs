@@ -0,0 +1,8 @@
+/// Errors that can occur while scheduling transactions.
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum SchedulerError {
+    #[error("scheduler disconnected from workers")]
+    DisconnectedSendChannel(&'static str),
+    #[error("scheduler disconnected from workers")]
+    DisconnectedRecvChannel(&'static str),
+}
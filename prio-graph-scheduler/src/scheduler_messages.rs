@@ -0,0 +1,50 @@
+use {
+    solana_sdk::{clock::Slot, transaction::SanitizedTransaction},
+    std::fmt::Display,
+};
+
+/// Simple wrapper type for tracking items created by [`IdGenerator`](crate::id_generator::IdGenerator).
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct TransactionId(u64);
+
+impl TransactionId {
+    pub fn new(index: u64) -> Self {
+        Self(index)
+    }
+}
+
+impl Display for TransactionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Simple wrapper type for tracking batches of transactions sent to a worker thread.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct TransactionBatchId(u64);
+
+impl TransactionBatchId {
+    pub fn new(index: u64) -> Self {
+        Self(index)
+    }
+}
+
+impl Display for TransactionBatchId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A unit of work sent from the scheduler to a consume-worker thread.
+pub struct ConsumeWork {
+    pub batch_id: TransactionBatchId,
+    pub ids: Vec<TransactionId>,
+    pub transactions: Vec<SanitizedTransaction>,
+    pub max_age_slots: Vec<Slot>,
+}
+
+/// The result of processing a [`ConsumeWork`], sent back to the scheduler.
+pub struct FinishedConsumeWork {
+    pub work: ConsumeWork,
+    pub retryable_indexes: Vec<usize>,
+}
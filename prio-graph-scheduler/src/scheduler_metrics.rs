@@ -0,0 +1,28 @@
+/// Counters tracked by the scheduler and periodically reported.
+#[derive(Default)]
+pub struct SchedulerCountMetrics {
+    /// Number of packets received from the sigverify/banking stage.
+    pub num_received: usize,
+    /// Number of packets that were buffered.
+    pub num_buffered: usize,
+    /// Number of packets scheduled for consumption.
+    pub num_scheduled: usize,
+    /// Number of packets dropped because the buffer was full.
+    pub num_dropped_on_capacity: usize,
+}
+
+impl SchedulerCountMetrics {
+    pub fn report(&self, name: &'static str) {
+        datapoint_info!(
+            name,
+            ("num_received", self.num_received, i64),
+            ("num_buffered", self.num_buffered, i64),
+            ("num_scheduled", self.num_scheduled, i64),
+            ("num_dropped_on_capacity", self.num_dropped_on_capacity, i64),
+        );
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -0,0 +1,127 @@
+use {solana_sdk::pubkey::Pubkey, std::collections::HashMap};
+
+/// Identifier for a scheduler thread, 0-indexed.
+pub type ThreadId = usize;
+
+/// A bitset over threads - up to 64 threads are supported.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct ThreadSet(u64);
+
+impl ThreadSet {
+    pub fn none() -> Self {
+        Self(0)
+    }
+
+    /// All threads in `0..num_threads`.
+    pub fn all(num_threads: usize) -> Self {
+        let mut set = Self::none();
+        for thread_id in 0..num_threads {
+            set.insert(thread_id);
+        }
+        set
+    }
+
+    pub fn only(thread_id: ThreadId) -> Self {
+        Self(1 << thread_id)
+    }
+
+    pub fn contains(&self, thread_id: ThreadId) -> bool {
+        self.0 & (1 << thread_id) != 0
+    }
+
+    pub fn insert(&mut self, thread_id: ThreadId) {
+        self.0 |= 1 << thread_id;
+    }
+
+    pub fn intersect(&self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn num_threads(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Returns the lowest thread id in the set, if any, so a caller that
+    /// doesn't need to balance load can just take the first option.
+    pub fn min_thread_id(&self) -> Option<ThreadId> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.0.trailing_zeros() as ThreadId)
+        }
+    }
+}
+
+/// A locked account is held exclusively by a single thread at a time - both
+/// reads and writes are treated as exclusive. This is more conservative
+/// than necessary (two readers on different threads don't actually
+/// conflict) but keeps conflict tracking simple and correct.
+#[derive(Debug, Default)]
+struct AccountLocks {
+    owner: Option<ThreadId>,
+}
+
+/// Tracks which thread currently holds a lock on which accounts, so the
+/// scheduler can avoid assigning conflicting transactions to different
+/// threads at the same time.
+pub struct ThreadAwareAccountLocks {
+    num_threads: usize,
+    locks: HashMap<Pubkey, AccountLocks>,
+}
+
+impl ThreadAwareAccountLocks {
+    pub fn new(num_threads: usize) -> Self {
+        Self {
+            num_threads,
+            locks: HashMap::new(),
+        }
+    }
+
+    /// Returns the set of threads that could take the given locks without
+    /// conflicting with any already-held lock: if none of the accounts are
+    /// currently locked, every thread is schedulable; if they are, only the
+    /// thread(s) that already own all of them are, and if two touched
+    /// accounts are owned by different threads, none are.
+    pub fn accounts_schedulable_threads<'a>(
+        &self,
+        write_account_locks: impl Iterator<Item = &'a Pubkey>,
+        read_account_locks: impl Iterator<Item = &'a Pubkey>,
+    ) -> ThreadSet {
+        let mut schedulable = ThreadSet::all(self.num_threads);
+        for account in write_account_locks.chain(read_account_locks) {
+            if let Some(owner) = self.locks.get(account).and_then(|locks| locks.owner) {
+                schedulable = schedulable.intersect(ThreadSet::only(owner));
+            }
+        }
+        schedulable
+    }
+
+    pub fn lock_accounts<'a>(
+        &mut self,
+        write_account_locks: impl Iterator<Item = &'a Pubkey>,
+        read_account_locks: impl Iterator<Item = &'a Pubkey>,
+        thread_id: ThreadId,
+    ) {
+        for account in write_account_locks.chain(read_account_locks) {
+            self.locks.entry(*account).or_default().owner = Some(thread_id);
+        }
+    }
+
+    /// Releases locks taken by [`Self::lock_accounts`], e.g. once a
+    /// worker has finished processing the batch that held them.
+    pub fn unlock_accounts<'a>(
+        &mut self,
+        write_account_locks: impl Iterator<Item = &'a Pubkey>,
+        read_account_locks: impl Iterator<Item = &'a Pubkey>,
+    ) {
+        for account in write_account_locks.chain(read_account_locks) {
+            if let std::collections::hash_map::Entry::Occupied(entry) = self.locks.entry(*account) {
+                entry.remove();
+            }
+        }
+    }
+}
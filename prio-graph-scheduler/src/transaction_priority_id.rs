@@ -0,0 +1,136 @@
+use {crate::scheduler_messages::TransactionId, std::cmp::Ordering};
+
+/// A transaction's scheduling priority, computed once at deserialization
+/// time from its compute-budget instructions and sender.
+///
+/// `priority` ranks by total reward (`compute_unit_price * compute_unit_limit`,
+/// saturating) rather than raw per-CU price, so a transaction can't buy its
+/// way to the front with a tiny compute-unit limit. `compute_unit_limit` is
+/// carried alongside it so downstream consumers (e.g. forwarding, which
+/// packs batches under a per-account CU cap) don't need to re-derive it.
+/// `sender_stake` is carried alongside for the same reason: it's consulted
+/// by [`PriorityComparator`] to break equal-priority ties, and shouldn't
+/// need to be re-resolved from a bank/stake-map lookup on every comparison.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TransactionPriorityDetails {
+    pub priority: u64,
+    pub compute_unit_limit: u64,
+    pub sender_stake: u64,
+}
+
+impl TransactionPriorityDetails {
+    pub fn new(compute_unit_price: u64, compute_unit_limit: u64, sender_stake: u64) -> Self {
+        Self {
+            priority: compute_unit_price.saturating_mul(compute_unit_limit),
+            compute_unit_limit,
+            sender_stake,
+        }
+    }
+}
+
+/// A transaction's priority paired with its id, used to order transactions
+/// within the container's priority queue without needing to look up the
+/// full transaction state.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TransactionPriorityId {
+    pub priority: u64,
+    pub id: TransactionId,
+}
+
+impl TransactionPriorityId {
+    pub fn new(priority: u64, id: TransactionId) -> Self {
+        Self { priority, id }
+    }
+}
+
+impl PartialOrd for TransactionPriorityId {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TransactionPriorityId {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+/// Orders two transactions for scheduling. The priority-graph/container
+/// machinery uses `TransactionPriorityId` for its O(log n) heap, but that
+/// only orders by the scalar `priority` (then by id); this trait is
+/// consulted to break ties (and, for alternative policies, to re-rank)
+/// among transactions of equal priority, so operators can swap in a policy other than the
+/// default without touching the scheduler itself.
+///
+/// Operates on the already-computed [`TransactionPriorityDetails`] rather
+/// than the raw packet, since that's what's stored on `TransactionState` -
+/// comparators run on every `sort_by` call during scheduling, so recomputing
+/// priority (or re-resolving sender stake) from the packet on every pairwise
+/// comparison would undermine the "computed once" rationale those fields
+/// exist for in the first place.
+pub trait PriorityComparator: Send + Sync {
+    fn compare(&self, a: &TransactionPriorityDetails, b: &TransactionPriorityDetails) -> Ordering;
+}
+
+/// Default ordering: `(priority, sender_stake)` lexicographic. Breaks
+/// equal-priority ties by descending sender stake, so zero-fee spam can't
+/// compete evenly with higher-staked senders.
+#[derive(Default)]
+pub struct DefaultPriorityComparator;
+
+impl PriorityComparator for DefaultPriorityComparator {
+    fn compare(&self, a: &TransactionPriorityDetails, b: &TransactionPriorityDetails) -> Ordering {
+        a.priority
+            .cmp(&b.priority)
+            .then_with(|| a.sender_stake.cmp(&b.sender_stake))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(priority: u64, sender_stake: u64) -> TransactionPriorityDetails {
+        TransactionPriorityDetails {
+            priority,
+            compute_unit_limit: 1,
+            sender_stake,
+        }
+    }
+
+    #[test]
+    fn test_default_comparator_orders_by_priority_first() {
+        let comparator = DefaultPriorityComparator;
+        let lower_priority = details(1, 100);
+        let higher_priority = details(2, 1);
+        assert_eq!(
+            comparator.compare(&lower_priority, &higher_priority),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_default_comparator_breaks_equal_priority_ties_by_stake() {
+        let comparator = DefaultPriorityComparator;
+        let low_stake = details(5, 1);
+        let high_stake = details(5, 100);
+        assert_eq!(
+            comparator.compare(&low_stake, &high_stake),
+            Ordering::Less
+        );
+        assert_eq!(
+            comparator.compare(&high_stake, &low_stake),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_default_comparator_equal_priority_and_stake() {
+        let comparator = DefaultPriorityComparator;
+        let a = details(5, 10);
+        let b = details(5, 10);
+        assert_eq!(comparator.compare(&a, &b), Ordering::Equal);
+    }
+}
@@ -0,0 +1,71 @@
+use {
+    crate::{
+        deserializable_packet::DeserializableTxPacket,
+        transaction_priority_id::TransactionPriorityDetails,
+    },
+    std::sync::Arc,
+};
+
+/// The state of a transaction held by a [`TransactionStateContainer`](crate::transaction_state_container::TransactionStateContainer).
+///
+/// A transaction starts out `Unprocessed`. When the scheduler hands it to a
+/// worker thread it moves to `Pending` until the worker reports back, at
+/// which point it is either retried (moved back to `Unprocessed`) or removed
+/// from the container entirely.
+pub enum TransactionState<Tx: DeserializableTxPacket> {
+    /// The transaction is available to be scheduled.
+    Unprocessed {
+        packet: Arc<Tx>,
+        priority_details: TransactionPriorityDetails,
+    },
+    /// The transaction has been scheduled to a worker thread and is awaiting
+    /// a result.
+    Pending {
+        packet: Arc<Tx>,
+        priority_details: TransactionPriorityDetails,
+    },
+}
+
+impl<Tx: DeserializableTxPacket> TransactionState<Tx> {
+    /// Returns a reference to the underlying packet, regardless of state.
+    pub fn packet(&self) -> &Arc<Tx> {
+        match self {
+            Self::Unprocessed { packet, .. } => packet,
+            Self::Pending { packet, .. } => packet,
+        }
+    }
+
+    /// Returns the priority details computed at deserialization time,
+    /// regardless of state.
+    pub fn priority_details(&self) -> TransactionPriorityDetails {
+        match self {
+            Self::Unprocessed {
+                priority_details, ..
+            } => *priority_details,
+            Self::Pending {
+                priority_details, ..
+            } => *priority_details,
+        }
+    }
+
+    /// Transitions the state from `Unprocessed` to `Pending`, returning the packet.
+    pub fn transition_to_pending(&mut self) -> Arc<Tx> {
+        let packet = self.packet().clone();
+        let priority_details = self.priority_details();
+        *self = Self::Pending {
+            packet: packet.clone(),
+            priority_details,
+        };
+        packet
+    }
+
+    /// Transitions the state from `Pending` back to `Unprocessed`, for retry.
+    pub fn retry(&mut self) {
+        let packet = self.packet().clone();
+        let priority_details = self.priority_details();
+        *self = Self::Unprocessed {
+            packet,
+            priority_details,
+        };
+    }
+}
@@ -0,0 +1,268 @@
+use {
+    crate::{
+        deserializable_packet::DeserializableTxPacket,
+        id_generator::IdGenerator,
+        scheduler_messages::TransactionId,
+        transaction_priority_id::{TransactionPriorityDetails, TransactionPriorityId},
+        transaction_state::TransactionState,
+    },
+    min_max_heap::MinMaxHeap,
+    std::{collections::HashMap, sync::Arc},
+};
+
+/// Holds all unprocessed and in-flight transaction state for the scheduler.
+///
+/// Transactions are keyed by an opaque [`TransactionId`], with a priority
+/// queue of [`TransactionPriorityId`] used to pop the highest-priority
+/// transaction without needing to scan the full map. The queue is backed by
+/// a min-max heap, which gives O(1) access to both the highest-priority
+/// entry (for scheduling) and the lowest-priority entry (for eviction when
+/// the container is at a fixed capacity), with O(log n) insert/remove.
+pub struct TransactionStateContainer<Tx: DeserializableTxPacket> {
+    id_generator: IdGenerator,
+    priority_queue: MinMaxHeap<TransactionPriorityId>,
+    id_to_transaction_state: HashMap<TransactionId, TransactionState<Tx>>,
+    /// Maximum number of transactions to hold at once. `None` means
+    /// unbounded.
+    capacity: Option<usize>,
+}
+
+impl<Tx: DeserializableTxPacket> Default for TransactionStateContainer<Tx> {
+    fn default() -> Self {
+        Self {
+            id_generator: IdGenerator::default(),
+            priority_queue: MinMaxHeap::new(),
+            id_to_transaction_state: HashMap::new(),
+            capacity: None,
+        }
+    }
+}
+
+impl<Tx: DeserializableTxPacket> TransactionStateContainer<Tx> {
+    /// Creates a container with a fixed capacity. Once `capacity` entries
+    /// are buffered, inserting a new transaction will evict the
+    /// lowest-priority entry if the incoming transaction outranks it, or be
+    /// rejected otherwise.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            id_generator: IdGenerator::default(),
+            priority_queue: MinMaxHeap::with_capacity(capacity),
+            id_to_transaction_state: HashMap::with_capacity(capacity),
+            capacity: Some(capacity),
+        }
+    }
+
+    /// Returns the fixed capacity this container was created with, or
+    /// `None` if it's unbounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.id_to_transaction_state.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.id_to_transaction_state.is_empty()
+    }
+
+    /// Inserts a new transaction with the given priority details, returning
+    /// its newly-generated id. This is only valid for unbounded containers;
+    /// a fixed-capacity container must use [`Self::push_with_eviction`].
+    pub fn insert_new_transaction(
+        &mut self,
+        packet: Arc<Tx>,
+        priority_details: TransactionPriorityDetails,
+    ) -> TransactionId {
+        debug_assert!(
+            self.capacity.is_none(),
+            "use push_with_eviction on a fixed-capacity container"
+        );
+        self.insert_new_transaction_unchecked(packet, priority_details)
+    }
+
+    /// Inserts a new transaction into a fixed-capacity container.
+    ///
+    /// If the container is below capacity, the transaction is always
+    /// inserted. If the container is at capacity, the incoming priority is
+    /// compared against the current lowest-priority entry: if the incoming
+    /// transaction has a higher priority, the lowest-priority entry is
+    /// evicted (its state is removed and dropped) to make room; otherwise
+    /// the incoming transaction is rejected.
+    ///
+    /// Returns `(inserted, evicted)`: `inserted` is the id of the newly
+    /// buffered transaction, if it was accepted; `evicted` is the id of the
+    /// transaction that was dropped to make room, if any.
+    pub fn push_with_eviction(
+        &mut self,
+        packet: Arc<Tx>,
+        priority_details: TransactionPriorityDetails,
+    ) -> (Option<TransactionId>, Option<TransactionId>) {
+        let capacity = self
+            .capacity
+            .expect("push_with_eviction requires a fixed-capacity container");
+
+        if self.len() < capacity {
+            let id = self.insert_new_transaction_unchecked(packet, priority_details);
+            return (Some(id), None);
+        }
+
+        // At capacity. `peek_min` only sees transactions still sitting in
+        // the priority queue - one that's been `pop()`'d for scheduling but
+        // not yet removed from `id_to_transaction_state` (i.e. pending) no
+        // longer has an entry there. If every buffered transaction is
+        // currently pending, there's nothing left to evict, so the new
+        // transaction must be rejected rather than silently exceeding
+        // capacity.
+        let Some(min) = self.priority_queue.peek_min().copied() else {
+            return (None, None);
+        };
+
+        if priority_details.priority <= min.priority {
+            return (None, None);
+        }
+
+        self.priority_queue.pop_min();
+        self.id_to_transaction_state.remove(&min.id);
+
+        let id = self.insert_new_transaction_unchecked(packet, priority_details);
+        (Some(id), Some(min.id))
+    }
+
+    fn insert_new_transaction_unchecked(
+        &mut self,
+        packet: Arc<Tx>,
+        priority_details: TransactionPriorityDetails,
+    ) -> TransactionId {
+        let id = TransactionId::new(self.id_generator.next());
+        self.priority_queue
+            .push(TransactionPriorityId::new(priority_details.priority, id));
+        self.id_to_transaction_state.insert(
+            id,
+            TransactionState::Unprocessed {
+                packet,
+                priority_details,
+            },
+        );
+        id
+    }
+
+    /// Pops the highest-priority transaction id, if any remain.
+    pub fn pop(&mut self) -> Option<TransactionPriorityId> {
+        self.priority_queue.pop_max()
+    }
+
+    pub fn get_transaction_state(&self, id: TransactionId) -> Option<&TransactionState<Tx>> {
+        self.id_to_transaction_state.get(&id)
+    }
+
+    pub fn get_mut_transaction_state(
+        &mut self,
+        id: TransactionId,
+    ) -> Option<&mut TransactionState<Tx>> {
+        self.id_to_transaction_state.get_mut(&id)
+    }
+
+    /// Removes a transaction's state from the container, e.g. once it has
+    /// been committed or permanently dropped. Only the id-to-state map is
+    /// updated; an entry still waiting in the priority queue is not removed.
+    pub fn remove_by_id(&mut self, id: TransactionId) -> Option<TransactionState<Tx>> {
+        self.id_to_transaction_state.remove(&id)
+    }
+
+    /// Re-queues a transaction that was popped but not scheduled, so it can
+    /// be considered again.
+    pub fn push_id_into_queue(&mut self, priority_id: TransactionPriorityId) {
+        self.priority_queue.push(priority_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::tests::MockImmutableDeserializedPacket,
+        solana_perf::packet::Packet,
+        solana_sdk::{
+            hash::Hash, pubkey::Pubkey, signature::Keypair, system_transaction,
+            transaction::{SanitizedVersionedTransaction, VersionedTransaction},
+        },
+    };
+
+    fn mock_packet(compute_unit_price: u64, compute_unit_limit: u32) -> Arc<MockImmutableDeserializedPacket> {
+        let from = Keypair::new();
+        let to = Pubkey::new_unique();
+        let transaction = system_transaction::transfer(&from, &to, 1, Hash::default());
+        let sanitized =
+            SanitizedVersionedTransaction::try_from(VersionedTransaction::from(transaction)).unwrap();
+        Arc::new(MockImmutableDeserializedPacket {
+            original_packet: Packet::default(),
+            transaction: sanitized,
+            message_hash: Hash::default(),
+            is_simple_vote: false,
+            compute_unit_price,
+            compute_unit_limit,
+            sender_stake: 0,
+        })
+    }
+
+    fn priority_details(compute_unit_price: u64, compute_unit_limit: u64) -> TransactionPriorityDetails {
+        TransactionPriorityDetails::new(compute_unit_price, compute_unit_limit, 0)
+    }
+
+    #[test]
+    fn test_push_with_eviction_below_capacity_always_inserts() {
+        let mut container = TransactionStateContainer::with_capacity(2);
+        let (inserted, evicted) = container.push_with_eviction(mock_packet(1, 1), priority_details(1, 1));
+        assert!(inserted.is_some());
+        assert!(evicted.is_none());
+        assert_eq!(container.len(), 1);
+    }
+
+    #[test]
+    fn test_push_with_eviction_evicts_lowest_priority_when_full() {
+        let mut container = TransactionStateContainer::with_capacity(2);
+        let (low_id, _) = container.push_with_eviction(mock_packet(1, 1), priority_details(1, 1));
+        let (high_id, _) = container.push_with_eviction(mock_packet(10, 1), priority_details(10, 1));
+        assert!(low_id.is_some());
+        assert!(high_id.is_some());
+
+        // Container is now full; a transaction with a higher priority than
+        // the current minimum should evict it.
+        let (inserted, evicted) = container.push_with_eviction(mock_packet(20, 1), priority_details(20, 1));
+        assert!(inserted.is_some());
+        assert_eq!(evicted, low_id);
+        assert_eq!(container.len(), 2);
+        assert!(container.get_transaction_state(low_id.unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_push_with_eviction_rejects_lower_priority_when_full() {
+        let mut container = TransactionStateContainer::with_capacity(2);
+        container.push_with_eviction(mock_packet(10, 1), priority_details(10, 1));
+        container.push_with_eviction(mock_packet(20, 1), priority_details(20, 1));
+
+        let (inserted, evicted) = container.push_with_eviction(mock_packet(1, 1), priority_details(1, 1));
+        assert!(inserted.is_none());
+        assert!(evicted.is_none());
+        assert_eq!(container.len(), 2);
+    }
+
+    #[test]
+    fn test_push_with_eviction_rejects_when_all_buffered_are_pending() {
+        let mut container = TransactionStateContainer::with_capacity(1);
+        container.push_with_eviction(mock_packet(10, 1), priority_details(10, 1));
+
+        // Popping removes the only entry from the priority queue (it's now
+        // pending/in-flight) without removing it from `id_to_transaction_state`,
+        // so the container is still considered full but has nothing left to
+        // evict.
+        assert!(container.pop().is_some());
+        assert_eq!(container.len(), 1);
+
+        let (inserted, evicted) = container.push_with_eviction(mock_packet(100, 1), priority_details(100, 1));
+        assert!(inserted.is_none());
+        assert!(evicted.is_none());
+        assert_eq!(container.len(), 1);
+    }
+}